@@ -12,6 +12,8 @@ pub async fn get_info(State(state): State<Arc<AppState>>) -> Json<serde_json::Va
         "threads": state.config.threads,
         "endpoints": {
             "POST /transcribe": "Transcribe audio file",
+            "POST /translate": "Translate audio file to English",
+            "GET /stream": "Stream incremental transcription over a WebSocket",
             "GET /health": "Health check",
             "GET /info": "API information",
             "GET /models": "List available model files"
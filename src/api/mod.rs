@@ -0,0 +1,4 @@
+pub mod info;
+pub mod models;
+pub mod stream;
+pub mod transcribe;
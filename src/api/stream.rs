@@ -0,0 +1,167 @@
+use crate::{
+    error::{AppError, AppResult},
+    whisper::{Task, TranscriptionResult, WhisperContext},
+    AppState,
+};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How often to run a partial transcription over the buffered audio if the
+/// client doesn't send an explicit `flush` control message.
+const AUTO_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The client is expected to stream raw PCM at this format.
+const STREAM_SAMPLE_RATE: u32 = 16_000;
+const STREAM_CHANNELS: u16 = 1;
+const STREAM_BITS_PER_SAMPLE: u16 = 16;
+
+/// Cap on the per-connection buffer (~30s of 16-bit mono PCM at 16 kHz).
+/// Once exceeded, a flush is forced as if the client had sent `"flush"`,
+/// so a client that streams without ever flushing can't grow memory or
+/// per-flush inference time without bound.
+const MAX_BUFFER_BYTES: usize = 30 * STREAM_SAMPLE_RATE as usize * 2;
+
+/// Upgrade to a WebSocket and stream incremental transcriptions back to the
+/// client as it sends raw 16 kHz mono PCM audio.
+///
+/// Binary frames are appended to a per-connection buffer. A text `"flush"`
+/// message (or the auto-flush timer, or the buffer hitting
+/// [`MAX_BUFFER_BYTES`]) transcribes everything buffered since the last
+/// flush and clears it, so later flushes only re-run inference over new
+/// audio rather than the whole session so far; `"commit"` or `"end"`
+/// flushes one last time and closes the connection.
+pub async fn stream(ws: WebSocketUpgrade, State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(socket, state.whisper.clone()))
+}
+
+async fn handle_socket(mut socket: WebSocket, whisper: Arc<WhisperContext>) {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut interval = tokio::time::interval(AUTO_FLUSH_INTERVAL);
+    interval.tick().await; // the first tick fires immediately; discard it
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if !buffer.is_empty() {
+                    flush(&mut socket, &whisper, &mut buffer, "partial").await;
+                }
+            }
+            incoming = socket.recv() => {
+                let Some(Ok(msg)) = incoming else { break };
+
+                match msg {
+                    Message::Binary(data) => {
+                        buffer.extend_from_slice(&data);
+                        if buffer.len() >= MAX_BUFFER_BYTES {
+                            flush(&mut socket, &whisper, &mut buffer, "partial").await;
+                        }
+                    }
+                    Message::Text(text) => match text.trim() {
+                        "flush" => flush(&mut socket, &whisper, &mut buffer, "partial").await,
+                        "commit" | "end" => {
+                            flush(&mut socket, &whisper, &mut buffer, "final").await;
+                            break;
+                        }
+                        other => tracing::warn!("Ignoring unknown stream control message: {}", other),
+                    },
+                    Message::Close(_) => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let _ = socket.close().await;
+}
+
+/// Transcribe everything buffered since the last flush and send it back as
+/// a JSON event, then clear the buffer.
+async fn flush(
+    socket: &mut WebSocket,
+    whisper: &Arc<WhisperContext>,
+    buffer: &mut Vec<u8>,
+    event_type: &'static str,
+) {
+    let window = std::mem::take(buffer);
+    let payload = match transcribe_buffer(whisper.clone(), window).await {
+        Ok(result) => render_event(event_type, &result),
+        Err(e) => json!({ "type": "error", "error": e.to_string() }),
+    };
+    let _ = socket.send(Message::Text(payload.to_string())).await;
+}
+
+fn render_event(event_type: &str, result: &TranscriptionResult) -> serde_json::Value {
+    let segments: Vec<_> = result
+        .segments
+        .iter()
+        .map(|seg| {
+            json!({
+                "start": seg.start as f64 / 100.0,
+                "end": seg.end as f64 / 100.0,
+                "text": result.text[seg.text_start..seg.text_end].trim(),
+            })
+        })
+        .collect();
+
+    json!({
+        "type": event_type,
+        "text": result.text,
+        "segments": segments,
+        "language": result.language,
+    })
+}
+
+/// Run (blocking, Mutex-guarded) inference off the async executor via
+/// `spawn_blocking`, so a slow transcription pass doesn't stall this
+/// connection's ability to read further frames or control messages.
+async fn transcribe_buffer(whisper: Arc<WhisperContext>, buffer: Vec<u8>) -> AppResult<TranscriptionResult> {
+    if buffer.is_empty() {
+        return Err(AppError::InvalidInput("No audio buffered yet".to_string()));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let wav = wrap_pcm_as_wav(
+            &buffer,
+            STREAM_SAMPLE_RATE,
+            STREAM_CHANNELS,
+            STREAM_BITS_PER_SAMPLE,
+        );
+        whisper.transcribe(&wav, None, Task::Transcribe)
+    })
+    .await
+    .map_err(|e| AppError::InternalError(format!("Transcription task panicked: {}", e)))?
+}
+
+/// Wrap raw little-endian PCM samples in a minimal WAV container so they can
+/// be fed through the existing WAV-parsing transcription path.
+fn wrap_pcm_as_wav(pcm: &[u8], sample_rate: u32, channels: u16, bits_per_sample: u16) -> Vec<u8> {
+    let byte_rate = sample_rate * channels as u32 * (bits_per_sample as u32 / 8);
+    let block_align = channels * (bits_per_sample / 8);
+    let data_len = pcm.len() as u32;
+    let riff_len = 36 + data_len;
+
+    let mut wav = Vec::with_capacity(44 + pcm.len());
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&riff_len.to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&bits_per_sample.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.extend_from_slice(pcm);
+    wav
+}
@@ -1,11 +1,16 @@
-use crate::{error::{AppError, AppResult}, whisper::TranscriptionResult, AppState};
+use crate::{
+    error::{AppError, AppResult},
+    whisper::{Task, TranscriptionResult},
+    AppState,
+};
 use axum::{
     body::Bytes,
     extract::{FromRequest, Multipart, Query, Request, State},
-    http::{header::CONTENT_TYPE, StatusCode},
-    response::IntoResponse,
+    http::header::CONTENT_TYPE,
+    response::{IntoResponse, Response},
     Json,
 };
+use serde_json::json;
 use std::io::Write;
 use std::sync::Arc;
 use tempfile::NamedTempFile;
@@ -13,12 +18,31 @@ use tempfile::NamedTempFile;
 #[derive(serde::Deserialize)]
 pub struct TranscribeQuery {
     pub language: Option<String>,
+    #[serde(default)]
+    pub response_format: ResponseFormat,
+    #[serde(default)]
+    pub task: Task,
+}
+
+/// Output shape for a transcription, mirroring the response variants offered
+/// by typical hosted transcription APIs.
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseFormat {
+    #[default]
+    Json,
+    Text,
+    Srt,
+    Vtt,
+    VerboseJson,
 }
 
 #[derive(serde::Serialize)]
 pub struct TranscribeResponse {
     pub result: TranscriptionResult,
     pub processing_time_ms: u128,
+    /// Which backend ("local" or "remote") actually served this request.
+    pub engine: &'static str,
 }
 
 /// Transcribe audio from multipart form data or raw bytes
@@ -26,7 +50,26 @@ pub async fn transcribe(
     State(state): State<Arc<AppState>>,
     Query(query): Query<TranscribeQuery>,
     request: Request,
-) -> AppResult<impl IntoResponse> {
+) -> AppResult<Response> {
+    handle(state, query, request).await
+}
+
+/// Translate audio from multipart form data or raw bytes into English,
+/// regardless of the `task` query parameter.
+pub async fn translate(
+    State(state): State<Arc<AppState>>,
+    Query(mut query): Query<TranscribeQuery>,
+    request: Request,
+) -> AppResult<Response> {
+    query.task = Task::Translate;
+    handle(state, query, request).await
+}
+
+async fn handle(
+    state: Arc<AppState>,
+    query: TranscribeQuery,
+    request: Request,
+) -> AppResult<Response> {
     let start_time = std::time::Instant::now();
 
     let content_type = request
@@ -61,27 +104,162 @@ pub async fn transcribe(
         .write_all(&body)
         .map_err(|e| AppError::FileError(format!("Failed to write audio: {}", e)))?;
 
-    // Transcribe
-    let result = state
-        .whisper
-        .transcribe_file(temp_file.path(), query.language.as_deref())
-        .await?;
+    // Transcribe, falling back to the remote backend once if the primary
+    // (local) engine errors.
+    let (result, engine) = match state
+        .backend
+        .transcribe_file(temp_file.path(), query.language.as_deref(), query.task)
+        .await
+    {
+        Ok(result) => (result, state.backend.name()),
+        Err(err) => match &state.remote_fallback {
+            Some(remote) if remote.name() != state.backend.name() => {
+                tracing::warn!(
+                    "{} transcription backend failed ({}), falling back to {} backend",
+                    state.backend.name(),
+                    err,
+                    remote.name()
+                );
+                let result = remote
+                    .transcribe_file(temp_file.path(), query.language.as_deref(), query.task)
+                    .await?;
+                (result, remote.name())
+            }
+            _ => return Err(err),
+        },
+    };
 
     let processing_time_ms = start_time.elapsed().as_millis();
 
     tracing::info!(
-        "Transcription completed in {}ms: {} characters",
+        "Transcription completed in {}ms via {} engine: {} characters",
         processing_time_ms,
+        engine,
         result.text.len()
     );
 
-    Ok((
-        StatusCode::OK,
-        Json(TranscribeResponse {
+    // Remote backends don't return per-segment timing, so cue-based formats
+    // would otherwise silently render as an empty-but-200 subtitle file.
+    let needs_segments = matches!(
+        query.response_format,
+        ResponseFormat::Srt | ResponseFormat::Vtt | ResponseFormat::VerboseJson
+    );
+    if needs_segments && engine != "local" {
+        return Err(AppError::InvalidInput(format!(
+            "response_format={:?} requires segment-level timing, which the '{}' backend does not provide; request response_format=json or text instead",
+            query.response_format, engine
+        )));
+    }
+
+    let response = match query.response_format {
+        ResponseFormat::Json => Json(TranscribeResponse {
             result,
             processing_time_ms,
-        }),
-    ))
+            engine,
+        })
+        .into_response(),
+        ResponseFormat::Text => (
+            [(CONTENT_TYPE, "text/plain; charset=utf-8")],
+            result.text,
+        )
+            .into_response(),
+        ResponseFormat::Srt => (
+            [(CONTENT_TYPE, "application/x-subrip; charset=utf-8")],
+            to_srt(&result),
+        )
+            .into_response(),
+        ResponseFormat::Vtt => (
+            [(CONTENT_TYPE, "text/vtt; charset=utf-8")],
+            to_vtt(&result),
+        )
+            .into_response(),
+        ResponseFormat::VerboseJson => {
+            Json(to_verbose_json(&result, query.task, engine)).into_response()
+        }
+    };
+
+    Ok(response)
+}
+
+/// Render a transcription as SRT cues, numbered from 1.
+fn to_srt(result: &TranscriptionResult) -> String {
+    let mut out = String::new();
+    for (i, seg) in result.segments.iter().enumerate() {
+        let text = result.text[seg.text_start..seg.text_end].trim();
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_timestamp(seg.start, ','),
+            format_timestamp(seg.end, ','),
+            text
+        ));
+    }
+    out
+}
+
+/// Render a transcription as WebVTT cues.
+fn to_vtt(result: &TranscriptionResult) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for seg in &result.segments {
+        let text = result.text[seg.text_start..seg.text_end].trim();
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timestamp(seg.start, '.'),
+            format_timestamp(seg.end, '.'),
+            text
+        ));
+    }
+    out
+}
+
+/// Format a whisper.cpp centisecond timestamp as `HH:MM:SS<sep>mmm`.
+fn format_timestamp(centiseconds: i64, fractional_sep: char) -> String {
+    let millis_total = centiseconds * 10;
+    let hours = millis_total / 3_600_000;
+    let minutes = (millis_total % 3_600_000) / 60_000;
+    let seconds = (millis_total % 60_000) / 1_000;
+    let millis = millis_total % 1_000;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        hours, minutes, seconds, fractional_sep, millis
+    )
+}
+
+/// Build a verbose JSON payload with per-segment timing in seconds and the
+/// detected (or requested) language, similar to OpenAI's `verbose_json`.
+fn to_verbose_json(result: &TranscriptionResult, task: Task, engine: &str) -> serde_json::Value {
+    let segments: Vec<_> = result
+        .segments
+        .iter()
+        .map(|seg| {
+            json!({
+                "id": seg.id,
+                "start": seg.start as f64 / 100.0,
+                "end": seg.end as f64 / 100.0,
+                "text": result.text[seg.text_start..seg.text_end].trim(),
+            })
+        })
+        .collect();
+
+    let duration = result
+        .segments
+        .last()
+        .map(|seg| seg.end as f64 / 100.0)
+        .unwrap_or(0.0);
+
+    let task_name = match task {
+        Task::Transcribe => "transcribe",
+        Task::Translate => "translate",
+    };
+
+    json!({
+        "task": task_name,
+        "language": result.language,
+        "duration": duration,
+        "text": result.text,
+        "segments": segments,
+        "engine": engine,
+    })
 }
 
 async fn extract_multipart_audio(request: Request) -> AppResult<Bytes> {
@@ -107,3 +285,25 @@ async fn extract_multipart_audio(request: Request) -> AppResult<Bytes> {
         "No audio file found in multipart request".to_string(),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_timestamp_renders_srt_style() {
+        // 1h 2m 3s 450ms, as centiseconds.
+        let centiseconds = (3_723 * 100) + 45;
+        assert_eq!(format_timestamp(centiseconds, ','), "01:02:03,450");
+    }
+
+    #[test]
+    fn format_timestamp_renders_vtt_style() {
+        assert_eq!(format_timestamp(0, '.'), "00:00:00.000");
+    }
+
+    #[test]
+    fn format_timestamp_converts_centiseconds_to_milliseconds() {
+        assert_eq!(format_timestamp(1, ','), "00:00:00,010");
+    }
+}
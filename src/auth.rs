@@ -0,0 +1,35 @@
+use crate::{error::AppError, AppState};
+use axum::{
+    extract::{Request, State},
+    http::header::AUTHORIZATION,
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+/// Reject requests that don't carry a matching `Authorization: Bearer
+/// <token>` header when `config.api_token` is set. When it isn't set, every
+/// request passes through unauthenticated.
+pub async fn require_token(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(expected) = state.config.api_token.as_deref() else {
+        return Ok(next.run(request).await);
+    };
+
+    let provided = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    if provided == Some(expected) {
+        Ok(next.run(request).await)
+    } else {
+        Err(AppError::Unauthorized(
+            "Missing or invalid bearer token".to_string(),
+        ))
+    }
+}
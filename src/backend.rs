@@ -0,0 +1,129 @@
+use crate::{
+    error::{AppError, AppResult},
+    whisper::{Task, TranscriptionResult},
+};
+use std::path::Path;
+use std::time::Duration;
+
+/// How long to wait for the remote transcription API before giving up.
+/// Without this, a hung remote API would block both the "remote as primary
+/// backend" path and the "local failed, fall back to remote" path
+/// indefinitely.
+const REMOTE_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A transcription engine capable of turning an audio file into text.
+///
+/// Implemented by the local whisper.cpp-backed [`crate::whisper::WhisperContext`]
+/// and by [`RemoteBackend`], so `api::transcribe` can route a request to
+/// either without caring which one actually does the work.
+#[async_trait::async_trait]
+pub trait TranscriptionBackend: Send + Sync {
+    async fn transcribe_file(
+        &self,
+        file_path: &Path,
+        language: Option<&str>,
+        task: Task,
+    ) -> AppResult<TranscriptionResult>;
+
+    /// Short identifier surfaced to clients so they can tell which engine
+    /// served a request, e.g. after a fallback.
+    fn name(&self) -> &'static str;
+}
+
+/// HTTP client for an OpenAI/Deepgram-compatible remote transcription API.
+/// Used either as the configured primary backend, or as a one-shot fallback
+/// when the local engine errors.
+pub struct RemoteBackend {
+    client: reqwest::Client,
+    api_url: String,
+    api_key: Option<String>,
+}
+
+impl RemoteBackend {
+    pub fn new(api_url: String, api_key: Option<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REMOTE_REQUEST_TIMEOUT)
+            .build()
+            .expect("reqwest client with a timeout should always build");
+        Self {
+            client,
+            api_url,
+            api_key,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct RemoteTranscriptionResponse {
+    text: String,
+    #[serde(default)]
+    language: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl TranscriptionBackend for RemoteBackend {
+    fn name(&self) -> &'static str {
+        "remote"
+    }
+
+    async fn transcribe_file(
+        &self,
+        file_path: &Path,
+        language: Option<&str>,
+        task: Task,
+    ) -> AppResult<TranscriptionResult> {
+        let audio_bytes = tokio::fs::read(file_path)
+            .await
+            .map_err(|e| AppError::FileError(format!("Failed to read audio file: {}", e)))?;
+
+        let file_name = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("audio")
+            .to_string();
+
+        let part = reqwest::multipart::Part::bytes(audio_bytes).file_name(file_name);
+        let mut form = reqwest::multipart::Form::new().part("file", part);
+        if let Some(language) = language {
+            form = form.text("language", language.to_string());
+        }
+        form = form.text(
+            "task",
+            match task {
+                Task::Transcribe => "transcribe",
+                Task::Translate => "translate",
+            },
+        );
+
+        let mut request = self.client.post(&self.api_url).multipart(form);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            AppError::WhisperError(format!("Remote transcription request failed: {}", e))
+        })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::WhisperError(format!(
+                "Remote transcription backend returned {}: {}",
+                status, body
+            )));
+        }
+
+        let parsed: RemoteTranscriptionResponse = response.json().await.map_err(|e| {
+            AppError::WhisperError(format!(
+                "Failed to parse remote transcription response: {}",
+                e
+            ))
+        })?;
+
+        Ok(TranscriptionResult {
+            text: parsed.text,
+            segments: Vec::new(),
+            language: parsed.language.or_else(|| language.map(|l| l.to_string())),
+        })
+    }
+}
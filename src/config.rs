@@ -25,6 +25,60 @@ pub struct Config {
     /// Log level
     #[arg(long, default_value = "info", env = "RUST_LOG")]
     pub log_level: String,
+
+    /// Minimum silence gap (in milliseconds) that the VAD preprocessing stage
+    /// will cut out between speech segments
+    #[arg(long, default_value = "500", env = "WHISPER_VAD_MIN_SILENCE_MS")]
+    pub vad_min_silence_ms: u64,
+
+    /// Multiplier applied to the noise floor to set the VAD speech/silence
+    /// energy threshold
+    #[arg(long, default_value = "1.5", env = "WHISPER_VAD_THRESHOLD_FACTOR")]
+    pub vad_threshold_factor: f32,
+
+    /// Which transcription backend serves requests by default
+    #[arg(long, value_enum, default_value = "local", env = "WHISPER_BACKEND")]
+    pub backend: BackendKind,
+
+    /// Base URL of an OpenAI/Deepgram-compatible remote transcription API.
+    /// Required when `backend` is `remote`; also used as a one-shot fallback
+    /// if the local engine errors.
+    #[arg(long, env = "WHISPER_REMOTE_API_URL")]
+    pub remote_api_url: Option<String>,
+
+    /// Bearer token sent to the remote transcription API
+    #[arg(long, env = "WHISPER_REMOTE_API_KEY")]
+    pub remote_api_key: Option<String>,
+
+    /// Bearer token required on protected endpoints (e.g. `/transcribe`).
+    /// Leave unset to run the API without authentication.
+    #[arg(long, env = "WHISPER_API_TOKEN")]
+    pub api_token: Option<String>,
+
+    /// Path to a PEM-encoded TLS certificate. Set alongside `tls_key_path`
+    /// to serve HTTPS instead of plain HTTP.
+    #[arg(long, env = "WHISPER_TLS_CERT_PATH")]
+    pub tls_cert_path: Option<PathBuf>,
+
+    /// Path to the PEM-encoded private key matching `tls_cert_path`.
+    #[arg(long, env = "WHISPER_TLS_KEY_PATH")]
+    pub tls_key_path: Option<PathBuf>,
+
+    /// Comma-separated list of origins allowed to make cross-origin requests
+    /// (e.g. `https://app.example.com,https://admin.example.com`). Leave
+    /// unset to reject cross-origin requests entirely.
+    #[arg(long, env = "WHISPER_CORS_ALLOWED_ORIGINS", value_delimiter = ',')]
+    pub cors_allowed_origins: Option<Vec<String>>,
+}
+
+/// Which transcription engine should serve requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendKind {
+    /// Run inference locally via whisper.cpp
+    Local,
+    /// Route requests to the configured remote API
+    Remote,
 }
 
 impl Config {
@@ -17,6 +17,9 @@ pub enum AppError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
     #[error("File processing error: {0}")]
     FileError(String),
 
@@ -33,6 +36,7 @@ impl IntoResponse for AppError {
             AppError::WhisperError(ref msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
             AppError::ModelNotFound(ref msg) => (StatusCode::NOT_FOUND, msg.clone()),
             AppError::InvalidInput(ref msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            AppError::Unauthorized(ref msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
             AppError::FileError(ref msg) => (StatusCode::BAD_REQUEST, msg.clone()),
             AppError::InternalError(ref msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
             AppError::Other(ref err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
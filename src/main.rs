@@ -1,23 +1,34 @@
 use axum::{
     extract::DefaultBodyLimit,
+    http::HeaderValue,
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
+use axum_server::tls_rustls::RustlsConfig;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tower_http::cors::CorsLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tracing_subscriber;
 
 mod api;
+mod auth;
+mod backend;
 mod config;
 mod error;
 mod whisper;
 
-use config::Config;
-use whisper::WhisperContext;
+use backend::{RemoteBackend, TranscriptionBackend};
+use config::{BackendKind, Config};
+use whisper::{VadConfig, WhisperContext};
 
 pub struct AppState {
     config: Config,
+    backend: Arc<dyn TranscriptionBackend>,
+    remote_fallback: Option<Arc<dyn TranscriptionBackend>>,
+    /// The local whisper.cpp engine, kept around (independent of `backend`)
+    /// for the `/stream` endpoint, which needs direct access to run
+    /// inference over in-memory sample buffers rather than uploaded files.
     whisper: Arc<WhisperContext>,
 }
 
@@ -35,33 +46,105 @@ async fn main() -> anyhow::Result<()> {
     let config = Config::load()?;
     tracing::info!("Configuration loaded: {:?}", config);
 
-    // Initialize Whisper context
-    let whisper = Arc::new(WhisperContext::new(&config.model_path)?);
+    // Initialize the local Whisper backend
+    let vad = VadConfig {
+        min_silence_ms: config.vad_min_silence_ms,
+        threshold_factor: config.vad_threshold_factor,
+    };
+    let whisper = Arc::new(WhisperContext::new(&config.model_path, vad)?);
     tracing::info!("Whisper model loaded from: {}", config.model_path.display());
 
-    let state = AppState { config, whisper };
+    // Initialize the remote backend, if configured; it serves either as the
+    // active backend or as a one-shot fallback when the local engine errors.
+    let remote_fallback: Option<Arc<dyn TranscriptionBackend>> =
+        config.remote_api_url.clone().map(|url| {
+            Arc::new(RemoteBackend::new(url, config.remote_api_key.clone()))
+                as Arc<dyn TranscriptionBackend>
+        });
 
-    // Build router
-    let app = Router::new()
-        .route("/health", get(health_check))
+    let backend: Arc<dyn TranscriptionBackend> = match config.backend {
+        BackendKind::Local => whisper.clone(),
+        BackendKind::Remote => remote_fallback.clone().ok_or_else(|| {
+            anyhow::anyhow!("config.backend is \"remote\" but remote_api_url is not set")
+        })?,
+    };
+
+    let addr: SocketAddr = format!("{}:{}", config.host, config.port).parse()?;
+    let tls_paths = config
+        .tls_cert_path
+        .clone()
+        .zip(config.tls_key_path.clone());
+
+    let state = Arc::new(AppState {
+        config,
+        backend,
+        remote_fallback,
+        whisper,
+    });
+
+    // Routes that require a valid bearer token when `api_token` is configured.
+    let protected = Router::new()
         .route("/transcribe", post(api::transcribe::transcribe))
+        .route("/translate", post(api::transcribe::translate))
+        .route("/stream", get(api::stream::stream))
         .route("/info", get(api::info::get_info))
         .route("/models", get(api::models::list_models))
-        .layer(DefaultBodyLimit::max(100 * 1024 * 1024))
-        .layer(CorsLayer::permissive())
-        .with_state(Arc::new(state));
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_token,
+        ));
 
-    // Start server
-    let listener = tokio::net::TcpListener::bind(&format!("{}:{}", "0.0.0.0", "8000")).await?;
+    let cors = cors_layer(&state.config.cors_allowed_origins);
 
-    let server_addr = listener.local_addr()?;
-    tracing::info!("🚀 Server listening on http://{}", server_addr);
+    // Build router
+    let app = Router::new()
+        .route("/health", get(health_check))
+        .merge(protected)
+        .layer(DefaultBodyLimit::max(100 * 1024 * 1024))
+        .layer(cors)
+        .with_state(state);
 
-    axum::serve(listener, app).await?;
+    // Start server, serving HTTPS via rustls when a cert/key pair is
+    // configured and falling back to plain HTTP otherwise.
+    match tls_paths {
+        Some((cert_path, key_path)) => {
+            let tls_config = RustlsConfig::from_pem_file(cert_path, key_path).await?;
+            tracing::info!("🔒 Server listening on https://{}", addr);
+            axum_server::bind_rustls(addr, tls_config)
+                .serve(app.into_make_service())
+                .await?;
+        }
+        None => {
+            let listener = tokio::net::TcpListener::bind(&addr).await?;
+            tracing::info!("🚀 Server listening on http://{}", addr);
+            axum::serve(listener, app).await?;
+        }
+    }
 
     Ok(())
 }
 
+/// Build the CORS policy from `cors_allowed_origins`. With no origins
+/// configured, cross-origin requests are rejected entirely, since this API
+/// may carry a bearer token and be exposed beyond localhost.
+fn cors_layer(allowed_origins: &Option<Vec<String>>) -> CorsLayer {
+    let origins: Vec<HeaderValue> = allowed_origins
+        .iter()
+        .flatten()
+        .filter_map(|origin| origin.parse().ok())
+        .collect();
+
+    let layer = CorsLayer::new()
+        .allow_methods(Any)
+        .allow_headers(Any);
+
+    if origins.is_empty() {
+        layer.allow_origin(AllowOrigin::list(Vec::<HeaderValue>::new()))
+    } else {
+        layer.allow_origin(AllowOrigin::list(origins))
+    }
+}
+
 async fn health_check() -> impl IntoResponse {
     Json(serde_json::json!({
         "status": "ok",
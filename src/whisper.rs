@@ -1,18 +1,43 @@
+use crate::backend::TranscriptionBackend;
 use crate::error::{AppError, AppResult};
-use std::path::{Path, PathBuf};
-use std::process::Command;
+use realfft::RealFftPlanner;
+use std::path::Path;
 use std::sync::Mutex;
 use whisper_rs::{WhisperContext as WhisperCtx, WhisperContextParameters};
 
+/// Sample rate Whisper models are trained on; all decoded audio is resampled
+/// to this rate before inference.
+const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// VAD analysis frame: 30 ms at 16 kHz.
+const VAD_FRAME_LEN: usize = 480;
+/// VAD hop between frames: 10 ms at 16 kHz.
+const VAD_HOP_LEN: usize = 160;
+/// Padding kept around each retained speech segment: 200 ms at 16 kHz.
+const VAD_PAD_SAMPLES: usize = 3_200;
+
+/// Tunables for the energy-based VAD preprocessing stage.
+#[derive(Debug, Clone, Copy)]
+pub struct VadConfig {
+    /// Silence gaps shorter than this are kept as part of the surrounding
+    /// speech segment instead of being cut out.
+    pub min_silence_ms: u64,
+    /// Multiplier applied to the noise floor (10th-percentile frame energy)
+    /// to decide the speech/silence energy threshold.
+    pub threshold_factor: f32,
+}
+
 /// Wrapper around whisper.cpp context with thread-safe initialization
 pub struct WhisperContext {
     context: Mutex<WhisperCtx>,
+    vad: VadConfig,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct TranscriptionResult {
     pub text: String,
     pub segments: Vec<Segment>,
+    pub language: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -24,9 +49,19 @@ pub struct Segment {
     pub text_end: usize,
 }
 
+/// Which whisper.cpp task to run: transcribe in the spoken language, or
+/// translate straight to English.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Task {
+    #[default]
+    Transcribe,
+    Translate,
+}
+
 impl WhisperContext {
     /// Create a new Whisper context from a model file
-    pub fn new<P: AsRef<Path>>(model_path: P) -> AppResult<Self> {
+    pub fn new<P: AsRef<Path>>(model_path: P, vad: VadConfig) -> AppResult<Self> {
         let path = model_path.as_ref();
 
         // Check if model file exists
@@ -48,25 +83,42 @@ impl WhisperContext {
 
         Ok(WhisperContext {
             context: Mutex::new(context),
+            vad,
         })
     }
 
-    /// Transcribe audio from bytes.
+    /// Transcribe audio from WAV bytes.
     /// If `language` is `None`, Whisper will auto-detect the language.
+    /// `task` selects between transcription in the spoken language and
+    /// translation to English.
     pub fn transcribe(
         &self,
         audio: &[u8],
         language: Option<&str>,
+        task: Task,
     ) -> AppResult<TranscriptionResult> {
-        // Convert audio bytes to f32 samples
-        // Note: This assumes 16-bit PCM WAV format
         let samples = self.bytes_to_samples(audio)?;
+        self.run_inference(samples, language, task)
+    }
+
+    /// Run Whisper inference over already-decoded, 16 kHz mono samples.
+    ///
+    /// Silence is trimmed out first via [`Self::vad_trim`]; segment
+    /// timestamps are mapped back onto the original timeline afterwards so
+    /// callers never see the trimmed-audio clock.
+    fn run_inference(
+        &self,
+        samples: Vec<f32>,
+        language: Option<&str>,
+        task: Task,
+    ) -> AppResult<TranscriptionResult> {
+        let (trimmed_samples, speech_spans) = self.vad_trim(&samples);
 
-        // Run inference
         let mut params =
             whisper_rs::FullParams::new(whisper_rs::SamplingStrategy::Greedy { best_of: 1 });
 
         params.set_language(language);
+        params.set_translate(task == Task::Translate);
         params.set_print_realtime(false);
         params.set_print_progress(false);
         params.set_print_timestamps(false);
@@ -81,7 +133,7 @@ impl WhisperContext {
         })?;
 
         state
-            .full(params, &samples)
+            .full(params, &trimmed_samples)
             .map_err(|e| AppError::WhisperError(format!("Transcription failed: {:?}", e)))?;
 
         // Extract results
@@ -101,8 +153,8 @@ impl WhisperContext {
                     AppError::WhisperError(format!("Failed to get segment text: {:?}", e))
                 })?
                 .to_string();
-            let start = seg.start_timestamp();
-            let end = seg.end_timestamp();
+            let start = Self::remap_timestamp(&speech_spans, seg.start_timestamp());
+            let end = Self::remap_timestamp(&speech_spans, seg.end_timestamp());
 
             let text_start = full_text.len();
             full_text.push_str(&segment_text);
@@ -117,86 +169,40 @@ impl WhisperContext {
             });
         }
 
+        // Report the language that was actually used: the caller's hint if one was
+        // given, otherwise whatever whisper.cpp auto-detected for this audio.
+        let detected_language = language
+            .map(|l| l.to_string())
+            .or_else(|| whisper_rs::get_lang_str(state.full_lang_id()).map(|s| s.to_string()));
+
         Ok(TranscriptionResult {
             text: full_text,
             segments,
+            language: detected_language,
         })
     }
 
     /// Transcribe from audio file.
     /// If `language` is `None`, Whisper will auto-detect the language.
+    ///
+    /// WAV files are parsed directly; every other format (MP3, M4A, FLAC, ...)
+    /// is decoded in-process via Symphonia, so no `ffmpeg` binary is required.
     pub async fn transcribe_file(
         &self,
         file_path: &Path,
         language: Option<&str>,
+        task: Task,
     ) -> AppResult<TranscriptionResult> {
-        // Convert to WAV if needed (MP3, M4A, etc.)
-        let wav_path = self.ensure_wav_format(file_path).await?;
-
-        let audio_data = tokio::fs::read(&wav_path)
-            .await
-            .map_err(|e| AppError::FileError(format!("Failed to read audio file: {}", e)))?;
-
-        // Clean up temporary converted file if it was created
-        if wav_path != file_path {
-            let _ = tokio::fs::remove_file(&wav_path).await;
-        }
-
-        self.transcribe(&audio_data, language)
-    }
-
-    /// Ensure the audio file is in WAV format, converting if necessary
-    async fn ensure_wav_format(&self, file_path: &Path) -> AppResult<PathBuf> {
-        // Detect WAV format from file magic bytes instead of extension
         if Self::is_wav_file(file_path)? {
-            return Ok(file_path.to_path_buf());
+            let audio_data = tokio::fs::read(file_path)
+                .await
+                .map_err(|e| AppError::FileError(format!("Failed to read audio file: {}", e)))?;
+            return self.transcribe(&audio_data, language, task);
         }
 
-        // Need to convert to WAV using ffmpeg
-        if !Self::is_ffmpeg_available() {
-            return Err(AppError::InvalidInput(
-                "Non-WAV audio format detected and ffmpeg is not installed. \
-                 Either send WAV audio or install ffmpeg for format conversion."
-                    .to_string(),
-            ));
-        }
-
-        // Create temporary WAV file
-        let temp_dir = std::env::temp_dir();
-        let temp_wav = temp_dir.join(format!("whisper_convert_{}.wav", uuid::Uuid::new_v4()));
-
-        // Convert using ffmpeg
-        let output = Command::new("ffmpeg")
-            .args(&[
-                "-i",
-                file_path.to_string_lossy().as_ref(),
-                "-acodec",
-                "pcm_s16le",
-                "-ar",
-                "16000",
-                "-ac",
-                "1",
-                "-y", // Overwrite output file
-                temp_wav.to_string_lossy().as_ref(),
-            ])
-            .output()
-            .map_err(|e| {
-                AppError::FileError(format!(
-                    "Failed to convert audio with ffmpeg: {}. Make sure ffmpeg is installed.",
-                    e
-                ))
-            })?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(AppError::FileError(format!(
-                "Audio conversion failed. If using curl, make sure to use \
-                 -F file=@<path> or --data-binary @<path> instead of -d: {}",
-                stderr
-            )));
-        }
-
-        Ok(temp_wav)
+        let path = file_path.to_path_buf();
+        let samples = Self::decode_with_symphonia(&path)?;
+        self.run_inference(samples, language, task)
     }
 
     /// Check if a file is WAV format by reading magic bytes
@@ -211,47 +217,535 @@ impl WhisperContext {
         Ok(bytes_read >= 12 && &header[0..4] == b"RIFF" && &header[8..12] == b"WAVE")
     }
 
-    /// Check if ffmpeg is available
-    fn is_ffmpeg_available() -> bool {
-        Command::new("ffmpeg")
-            .arg("-version")
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+    /// Decode a non-WAV audio file (MP3, M4A, FLAC, ...) with Symphonia,
+    /// downmix to mono, and resample to Whisper's required 16 kHz.
+    fn decode_with_symphonia(file_path: &Path) -> AppResult<Vec<f32>> {
+        use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+        use symphonia::core::errors::Error as SymphoniaError;
+        use symphonia::core::formats::FormatOptions;
+        use symphonia::core::io::MediaSourceStream;
+        use symphonia::core::meta::MetadataOptions;
+        use symphonia::core::probe::Hint;
+
+        let file = std::fs::File::open(file_path)
+            .map_err(|e| AppError::FileError(format!("Failed to open audio file: {}", e)))?;
+        let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+        let mut hint = Hint::new();
+        if let Some(ext) = file_path.extension().and_then(|e| e.to_str()) {
+            hint.with_extension(ext);
+        }
+
+        let probed = symphonia::default::get_probe()
+            .format(
+                &hint,
+                mss,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )
+            .map_err(|e| AppError::InvalidInput(format!("Unrecognized audio format: {}", e)))?;
+
+        let mut format = probed.format;
+        let track = format
+            .tracks()
+            .iter()
+            .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+            .ok_or_else(|| AppError::InvalidInput("No audio track found in file".to_string()))?
+            .clone();
+
+        let source_rate = track.codec_params.sample_rate.ok_or_else(|| {
+            AppError::InvalidInput("Audio track has no sample rate".to_string())
+        })?;
+
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .map_err(|e| AppError::FileError(format!("Failed to create audio decoder: {}", e)))?;
+
+        let track_id = track.id;
+        let mut samples: Vec<f32> = Vec::new();
+
+        loop {
+            let packet = match format.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(ref e))
+                    if e.kind() == std::io::ErrorKind::UnexpectedEof =>
+                {
+                    break;
+                }
+                Err(e) => {
+                    return Err(AppError::FileError(format!(
+                        "Failed to read audio packet: {}",
+                        e
+                    )))
+                }
+            };
+
+            if packet.track_id() != track_id {
+                continue;
+            }
+
+            match decoder.decode(&packet) {
+                Ok(buf) => Self::append_downmixed(buf, &mut samples),
+                Err(SymphoniaError::DecodeError(_)) => continue,
+                Err(e) => {
+                    return Err(AppError::FileError(format!(
+                        "Failed to decode audio packet: {}",
+                        e
+                    )))
+                }
+            }
+        }
+
+        if samples.is_empty() {
+            return Err(AppError::InvalidInput(
+                "No audio data found in file".to_string(),
+            ));
+        }
+
+        Ok(Self::resample_linear(
+            &samples,
+            source_rate,
+            WHISPER_SAMPLE_RATE,
+        ))
+    }
+
+    /// Append an audio buffer's samples to `out`, averaging channels down to mono.
+    fn append_downmixed(buf: symphonia::core::audio::AudioBufferRef, out: &mut Vec<f32>) {
+        use symphonia::core::audio::{AudioBuffer, Signal};
+
+        let channels = buf.spec().channels.count();
+        let frames = buf.frames();
+        let mut planar: AudioBuffer<f32> = AudioBuffer::new(frames as u64, *buf.spec());
+        planar.render_reserved(Some(frames));
+        buf.convert(&mut planar);
+
+        for frame in 0..frames {
+            let mut sum = 0.0f32;
+            for ch in 0..channels {
+                sum += planar.chan(ch)[frame];
+            }
+            out.push(sum / channels as f32);
+        }
+    }
+
+    /// Linearly resample mono f32 samples from `src_rate` to `dst_rate`.
+    fn resample_linear(samples: &[f32], src_rate: u32, dst_rate: u32) -> Vec<f32> {
+        if src_rate == 0 || samples.is_empty() {
+            return Vec::new();
+        }
+        if src_rate == dst_rate {
+            return samples.to_vec();
+        }
+
+        let src_rate = src_rate as f64;
+        let dst_rate = dst_rate as f64;
+        let out_len = ((samples.len() as f64) * dst_rate / src_rate).ceil() as usize;
+        let mut out = Vec::with_capacity(out_len);
+
+        for i in 0..out_len {
+            let pos = i as f64 * src_rate / dst_rate;
+            let idx = pos.floor() as usize;
+            let frac = (pos - idx as f64) as f32;
+            let a = samples[idx.min(samples.len() - 1)];
+            let b = samples[(idx + 1).min(samples.len() - 1)];
+            out.push(a + (b - a) * frac);
+        }
+
+        out
     }
 
-    /// Convert raw audio bytes to f32 samples
-    /// Assumes 16-bit PCM WAV format
+    /// Parse a WAV file's `fmt `/`data` chunks, downmix to mono, and resample
+    /// to Whisper's required 16 kHz, instead of assuming 16-bit mono at 44
+    /// bytes in.
     fn bytes_to_samples(&self, audio_bytes: &[u8]) -> AppResult<Vec<f32>> {
-        // Skip WAV header (44 bytes for standard WAV)
-        let data_start = if audio_bytes.len() > 44
-            && &audio_bytes[0..4] == b"RIFF"
-            && &audio_bytes[8..12] == b"WAVE"
+        if audio_bytes.len() < 12 || &audio_bytes[0..4] != b"RIFF" || &audio_bytes[8..12] != b"WAVE"
         {
-            44
-        } else {
-            0
+            return Err(AppError::InvalidInput("Not a valid WAV file".to_string()));
+        }
+
+        let mut num_channels: u16 = 1;
+        let mut sample_rate: u32 = WHISPER_SAMPLE_RATE;
+        let mut bits_per_sample: u16 = 16;
+        let mut format_tag: u16 = 1;
+        let mut data: Option<&[u8]> = None;
+
+        // Walk RIFF chunks rather than assuming a fixed 44-byte header, since
+        // WAV files may carry extra chunks (e.g. `LIST`) before `data`.
+        let mut pos = 12;
+        while pos + 8 <= audio_bytes.len() {
+            let chunk_id = &audio_bytes[pos..pos + 4];
+            let chunk_size =
+                u32::from_le_bytes(audio_bytes[pos + 4..pos + 8].try_into().unwrap()) as usize;
+            let chunk_start = pos + 8;
+            let chunk_end = chunk_start.saturating_add(chunk_size).min(audio_bytes.len());
+
+            if chunk_id == b"fmt " && chunk_end.saturating_sub(chunk_start) >= 16 {
+                let fmt = &audio_bytes[chunk_start..chunk_end];
+                format_tag = u16::from_le_bytes([fmt[0], fmt[1]]);
+                num_channels = u16::from_le_bytes([fmt[2], fmt[3]]);
+                sample_rate = u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]);
+                bits_per_sample = u16::from_le_bytes([fmt[14], fmt[15]]);
+            } else if chunk_id == b"data" {
+                data = Some(&audio_bytes[chunk_start..chunk_end]);
+            }
+
+            // Chunks are padded to an even number of bytes.
+            pos = chunk_start + chunk_size + (chunk_size % 2);
+        }
+
+        let data = data
+            .ok_or_else(|| AppError::InvalidInput("WAV file has no data chunk".to_string()))?;
+
+        if num_channels == 0 {
+            return Err(AppError::InvalidInput(
+                "WAV file reports zero channels".to_string(),
+            ));
+        }
+
+        if sample_rate == 0 {
+            return Err(AppError::InvalidInput(
+                "WAV file reports a zero sample rate".to_string(),
+            ));
+        }
+
+        // We don't resolve the sub-format GUID of WAVE_FORMAT_EXTENSIBLE, so
+        // only plain PCM and IEEE-float `fmt ` tags are supported; anything
+        // else would otherwise be silently mis-decoded as raw integers.
+        let is_float = match format_tag {
+            1 => false,
+            3 => true,
+            other => {
+                return Err(AppError::InvalidInput(format!(
+                    "Unsupported WAV format tag {} (only PCM and IEEE float are supported)",
+                    other
+                )))
+            }
         };
 
-        if audio_bytes.len() <= data_start {
-            return Err(AppError::InvalidInput("Audio file too small".to_string()));
+        let bytes_per_sample = (bits_per_sample / 8) as usize;
+        let frame_size = bytes_per_sample * num_channels as usize;
+        if bytes_per_sample == 0 || data.len() < frame_size {
+            return Err(AppError::InvalidInput(format!(
+                "Unsupported or truncated WAV audio data (bit depth {})",
+                bits_per_sample
+            )));
         }
 
-        let audio_data = &audio_bytes[data_start..];
-        let samples: Vec<f32> = audio_data
-            .chunks_exact(2)
-            .map(|chunk| {
-                let sample = i16::from_le_bytes([chunk[0], chunk[1]]) as f32 / 32768.0;
-                sample.clamp(-1.0, 1.0)
+        let mono_samples: Vec<f32> = data
+            .chunks_exact(frame_size)
+            .map(|frame| {
+                let sum: f32 = frame
+                    .chunks_exact(bytes_per_sample)
+                    .map(|s| Self::decode_pcm_sample(s, bits_per_sample, is_float))
+                    .sum();
+                sum / num_channels as f32
             })
             .collect();
 
-        if samples.is_empty() {
+        if mono_samples.is_empty() {
             return Err(AppError::InvalidInput(
                 "No audio data found in file".to_string(),
             ));
         }
 
-        Ok(samples)
+        Ok(Self::resample_linear(
+            &mono_samples,
+            sample_rate,
+            WHISPER_SAMPLE_RATE,
+        ))
+    }
+
+    /// Decode a single little-endian sample to `[-1.0, 1.0]`. `is_float`
+    /// selects IEEE-754 decoding (WAV format tag `3`, as produced by many
+    /// DAWs/ffmpeg) instead of integer PCM (format tag `1`) — treating a
+    /// float sample as an integer silently reinterprets its bit pattern
+    /// into garbage.
+    fn decode_pcm_sample(bytes: &[u8], bits_per_sample: u16, is_float: bool) -> f32 {
+        let sample = if is_float {
+            match bits_per_sample {
+                32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+                64 => f64::from_le_bytes(bytes.try_into().unwrap()) as f32,
+                _ => 0.0,
+            }
+        } else {
+            match bits_per_sample {
+                8 => (bytes[0] as f32 - 128.0) / 128.0,
+                16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 32768.0,
+                24 => {
+                    let sign_extend = if bytes[2] & 0x80 != 0 { 0xFF } else { 0x00 };
+                    i32::from_le_bytes([bytes[0], bytes[1], bytes[2], sign_extend]) as f32
+                        / 8_388_608.0
+                }
+                32 => {
+                    i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32
+                        / 2_147_483_648.0
+                }
+                _ => 0.0,
+            }
+        };
+        sample.clamp(-1.0, 1.0)
+    }
+
+    /// Trim long silences out of `samples` using an energy-based VAD, and
+    /// return the concatenated speech audio alongside the spans needed to
+    /// map its timestamps back onto the original timeline.
+    ///
+    /// Frames are classified as speech when their spectral energy exceeds
+    /// the noise floor (10th-percentile frame energy) times
+    /// `self.vad.threshold_factor`. Retained segments are padded by
+    /// [`VAD_PAD_SAMPLES`] on each side, and gaps shorter than
+    /// `self.vad.min_silence_ms` are merged away rather than cut out.
+    fn vad_trim(&self, samples: &[f32]) -> (Vec<f32>, Vec<SpeechSpan>) {
+        Self::vad_trim_with_config(samples, self.vad)
+    }
+
+    /// The body of [`Self::vad_trim`], taking `vad` explicitly so it can be
+    /// unit-tested without a loaded whisper.cpp model.
+    fn vad_trim_with_config(samples: &[f32], vad: VadConfig) -> (Vec<f32>, Vec<SpeechSpan>) {
+        let identity = || {
+            (
+                samples.to_vec(),
+                vec![SpeechSpan {
+                    orig_start: 0,
+                    orig_end: samples.len(),
+                    trimmed_start: 0,
+                }],
+            )
+        };
+
+        if samples.len() < VAD_FRAME_LEN {
+            return identity();
+        }
+
+        let mut planner = RealFftPlanner::<f32>::new();
+        let fft = planner.plan_fft_forward(VAD_FRAME_LEN);
+
+        let mut frame_starts = Vec::new();
+        let mut energies = Vec::new();
+        let mut start = 0;
+        while start + VAD_FRAME_LEN <= samples.len() {
+            let mut input = samples[start..start + VAD_FRAME_LEN].to_vec();
+            let mut spectrum = fft.make_output_vec();
+            if fft.process(&mut input, &mut spectrum).is_ok() {
+                let energy: f32 = spectrum.iter().map(|c| c.norm_sqr()).sum();
+                energies.push(energy);
+                frame_starts.push(start);
+            }
+            start += VAD_HOP_LEN;
+        }
+
+        if energies.is_empty() {
+            return identity();
+        }
+
+        let mut sorted_energies = energies.clone();
+        // `total_cmp` gives a total order even over non-finite energies
+        // (e.g. from a Symphonia decode edge case), unlike
+        // `partial_cmp().unwrap()`, which panics on NaN.
+        sorted_energies.sort_by(|a, b| a.total_cmp(b));
+        let noise_floor = sorted_energies[sorted_energies.len() / 10];
+        let threshold = noise_floor * vad.threshold_factor;
+
+        // Merge consecutive speech frames into raw (start, end) sample ranges.
+        let mut raw_segments: Vec<(usize, usize)> = Vec::new();
+        let mut current: Option<(usize, usize)> = None;
+        for (i, &energy) in energies.iter().enumerate() {
+            let frame_start = frame_starts[i];
+            let frame_end = frame_start + VAD_FRAME_LEN;
+            if energy > threshold {
+                current = Some(match current {
+                    Some((seg_start, _)) => (seg_start, frame_end),
+                    None => (frame_start, frame_end),
+                });
+            } else if let Some(seg) = current.take() {
+                raw_segments.push(seg);
+            }
+        }
+        if let Some(seg) = current.take() {
+            raw_segments.push(seg);
+        }
+
+        if raw_segments.is_empty() {
+            return identity();
+        }
+
+        // Pad each segment, then merge any that are now separated by less
+        // than the configured minimum silence gap.
+        let min_silence_samples = vad.min_silence_ms as usize * (WHISPER_SAMPLE_RATE as usize / 1000);
+        let mut merged: Vec<(usize, usize)> = Vec::new();
+        for (seg_start, seg_end) in raw_segments {
+            let padded_start = seg_start.saturating_sub(VAD_PAD_SAMPLES);
+            let padded_end = (seg_end + VAD_PAD_SAMPLES).min(samples.len());
+
+            match merged.last_mut() {
+                Some((_, last_end)) if padded_start.saturating_sub(*last_end) < min_silence_samples => {
+                    *last_end = padded_end.max(*last_end);
+                }
+                _ => merged.push((padded_start, padded_end)),
+            }
+        }
+
+        let mut trimmed = Vec::with_capacity(samples.len());
+        let mut spans = Vec::with_capacity(merged.len());
+        for (seg_start, seg_end) in merged {
+            spans.push(SpeechSpan {
+                orig_start: seg_start,
+                orig_end: seg_end,
+                trimmed_start: trimmed.len(),
+            });
+            trimmed.extend_from_slice(&samples[seg_start..seg_end]);
+        }
+
+        (trimmed, spans)
+    }
+
+    /// Map a whisper.cpp centisecond timestamp, measured against the
+    /// silence-trimmed audio, back onto the original recording's timeline.
+    fn remap_timestamp(spans: &[SpeechSpan], trimmed_cs: i64) -> i64 {
+        let samples_per_cs = (WHISPER_SAMPLE_RATE as i64) / 100;
+        let trimmed_sample = (trimmed_cs.max(0) as usize) * samples_per_cs as usize;
+
+        for span in spans {
+            let span_len = span.orig_end - span.orig_start;
+            if trimmed_sample <= span.trimmed_start + span_len {
+                let offset = trimmed_sample.saturating_sub(span.trimmed_start).min(span_len);
+                return ((span.orig_start + offset) / samples_per_cs as usize) as i64;
+            }
+        }
+
+        spans
+            .last()
+            .map(|span| (span.orig_end / samples_per_cs as usize) as i64)
+            .unwrap_or(trimmed_cs)
+    }
+}
+
+/// A retained (padded) span of speech, in original-sample coordinates, and
+/// its offset into the concatenated, silence-trimmed signal.
+struct SpeechSpan {
+    orig_start: usize,
+    orig_end: usize,
+    trimmed_start: usize,
+}
+
+#[async_trait::async_trait]
+impl TranscriptionBackend for WhisperContext {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn transcribe_file(
+        &self,
+        file_path: &Path,
+        language: Option<&str>,
+        task: Task,
+    ) -> AppResult<TranscriptionResult> {
+        // Delegates to the inherent method of the same name, which Rust's
+        // method resolution prefers over this trait method.
+        self.transcribe_file(file_path, language, task).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resample_linear_is_a_no_op_at_the_same_rate() {
+        let samples = vec![0.1, -0.2, 0.3, -0.4];
+        assert_eq!(
+            WhisperContext::resample_linear(&samples, 16_000, 16_000),
+            samples
+        );
+    }
+
+    #[test]
+    fn resample_linear_upsamples_to_the_expected_length_and_endpoints() {
+        let samples = vec![0.0, 1.0];
+        let out = WhisperContext::resample_linear(&samples, 8_000, 16_000);
+        assert_eq!(out.len(), 4);
+        assert_eq!(out[0], 0.0);
+        assert!((out[3] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn resample_linear_downsamples_to_the_expected_length() {
+        let samples = vec![0.0, 0.25, 0.5, 0.75];
+        let out = WhisperContext::resample_linear(&samples, 16_000, 8_000);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0], 0.0);
+    }
+
+    #[test]
+    fn decode_pcm_sample_treats_format_tag_3_as_ieee_float() {
+        let bytes = 0.5f32.to_le_bytes();
+        assert_eq!(WhisperContext::decode_pcm_sample(&bytes, 32, true), 0.5);
+    }
+
+    #[test]
+    fn decode_pcm_sample_treats_format_tag_1_as_integer_pcm() {
+        let bytes = i16::MAX.to_le_bytes();
+        let sample = WhisperContext::decode_pcm_sample(&bytes, 16, false);
+        assert!((sample - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn resample_linear_rejects_a_zero_source_rate_instead_of_overflowing() {
+        // A crafted/corrupt `fmt ` chunk reporting sample_rate = 0 must not
+        // reach the `out_len` arithmetic that would otherwise compute
+        // `usize::MAX` and panic in `Vec::with_capacity`.
+        assert_eq!(
+            WhisperContext::resample_linear(&[0.1, 0.2, 0.3], 0, WHISPER_SAMPLE_RATE),
+            Vec::<f32>::new()
+        );
+    }
+
+    fn vad_test_signal() -> Vec<f32> {
+        let mut samples = Vec::new();
+        samples.extend(std::iter::repeat(0.0f32).take(3_000));
+        samples.extend(std::iter::repeat(0.8f32).take(960));
+        samples.extend(std::iter::repeat(0.0f32).take(20_000));
+        samples.extend(std::iter::repeat(0.8f32).take(960));
+        samples.extend(std::iter::repeat(0.0f32).take(3_000));
+        samples
+    }
+
+    #[test]
+    fn vad_trim_keeps_two_speech_segments_separate_beyond_min_silence() {
+        let vad = VadConfig {
+            min_silence_ms: 100,
+            threshold_factor: 1.5,
+        };
+        let (_, spans) = WhisperContext::vad_trim_with_config(&vad_test_signal(), vad);
+        assert_eq!(spans.len(), 2);
+    }
+
+    #[test]
+    fn vad_trim_merges_two_speech_segments_within_min_silence() {
+        let vad = VadConfig {
+            min_silence_ms: 2_000,
+            threshold_factor: 1.5,
+        };
+        let (_, spans) = WhisperContext::vad_trim_with_config(&vad_test_signal(), vad);
+        assert_eq!(spans.len(), 1);
+    }
+
+    #[test]
+    fn remap_timestamp_round_trips_into_the_second_span() {
+        let spans = vec![
+            SpeechSpan {
+                orig_start: 1_000,
+                orig_end: 2_000,
+                trimmed_start: 0,
+            },
+            SpeechSpan {
+                orig_start: 5_000,
+                orig_end: 6_000,
+                trimmed_start: 1_000,
+            },
+        ];
+        // 10 centiseconds = 1600 samples at 16 kHz, which falls inside the
+        // second span's trimmed range [1000, 2000).
+        assert_eq!(WhisperContext::remap_timestamp(&spans, 10), 35);
     }
 }